@@ -0,0 +1,71 @@
+//! Compares [`Scanner::take_whitespace`] with consuming whitespace by decoding
+//! every char, which is how it was implemented before the ASCII fast path.
+//!
+//! Run with `cargo bench --bench whitespace`.
+
+use lexlib::text::Scanner;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    // deeply indented ASCII source, like typical code
+    let ascii: String = (0..10_000)
+        .map(|i| format!("{}item = {i};\r\n", " ".repeat(4 * (i % 8))))
+        .collect();
+
+    // the same, with some Unicode whitespace mixed into the indentation
+    let mixed = ascii.replace("    item", "  \u{A0}\u{2003} item");
+
+    for (name, source) in [("ascii", &ascii), ("mixed", &mixed)] {
+        assert_eq!(
+            scan(source, Scanner::take_whitespace),
+            scan(source, |scanner| scanner.take_while(char::is_whitespace)),
+            "fast path must consume the same whitespace"
+        );
+
+        let decoded = time(|| scan(source, |scanner| scanner.take_while(char::is_whitespace)));
+        let fast = time(|| scan(source, Scanner::take_whitespace));
+
+        println!(
+            "{name}: decoded {decoded:?}, fast path {fast:?} ({:.2}x)",
+            decoded.as_secs_f64() / fast.as_secs_f64()
+        );
+    }
+
+    let mut scanner = Scanner::new("\u{A0}\t\u{2003}\nx");
+
+    assert_eq!(scanner.take_whitespace(), "\u{A0}\t\u{2003}\n");
+    assert_eq!((scanner.line(), scanner.column()), (2, 1));
+}
+
+/// Scans each line of a source string, consuming leading whitespace with
+/// `take`, and returns the total whitespace consumed and the final location.
+fn scan<'src>(
+    source: &'src str,
+    mut take: impl FnMut(&mut Scanner<'src>) -> &'src str,
+) -> (usize, usize, usize) {
+    let mut scanner = Scanner::new(source);
+    let mut total = 0;
+
+    while scanner.peek_char().is_some() {
+        total += take(&mut scanner).len();
+        scanner.skip_while(|ch| !ch.is_whitespace());
+    }
+
+    (total, scanner.line(), scanner.column())
+}
+
+/// Measures the average duration of a function over several iterations.
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+
+    start.elapsed() / ITERATIONS
+}
@@ -1,3 +1,9 @@
+mod pool;
+#[cfg(feature = "utf16")]
+mod utf16;
 mod utf8;
 
-pub use self::utf8::{Utf8CharReader, Utf8ChunkReader};
+pub use self::pool::{PooledBuffer, Utf8BufferPool};
+pub use self::utf8::{InvalidUtf8Error, Utf8CharReader, Utf8ChunkReader, read_utf8_checked};
+#[cfg(feature = "utf16")]
+pub use self::utf16::{Endianness, InvalidUtf16Error, Utf16ChunkReader};
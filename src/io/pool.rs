@@ -0,0 +1,110 @@
+use std::{fmt, mem, ops, sync::Mutex};
+
+/// A pool of reusable buffers for [`Utf8ChunkReader`][super::Utf8ChunkReader]
+/// and [`Utf8CharReader`][super::Utf8CharReader].
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::io::{Utf8BufferPool, Utf8ChunkReader};
+/// # fn main() -> std::io::Result<()> {
+/// let pool = Utf8BufferPool::new(8192);
+/// let mut buf = pool.get();
+/// let mut reader = Utf8ChunkReader::new(&mut buf, "example".as_bytes());
+///
+/// while reader.read_chunk()? {
+///     print!("{}", reader.chunk());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Performance
+///
+/// This struct is designed for services that create many short-lived readers,
+/// where allocating a new buffer for each one is wasteful. Buffers are returned
+/// to the pool when their [`PooledBuffer`] guard is dropped and are not cleared
+/// before being reused.
+pub struct Utf8BufferPool {
+    buffer_len: usize,
+    buffers: Mutex<Vec<Box<[u8]>>>,
+}
+
+/// A buffer borrowed from a [`Utf8BufferPool`], which is returned to the pool
+/// when dropped.
+pub struct PooledBuffer<'pool> {
+    pool: &'pool Utf8BufferPool,
+    buf: Box<[u8]>,
+}
+
+impl Utf8BufferPool {
+    /// Creates an empty pool of buffers that are `buffer_len` bytes long.
+    #[inline]
+    pub fn new(buffer_len: usize) -> Self {
+        Self {
+            buffer_len,
+            buffers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Gets the length of each buffer in the pool.
+    #[inline]
+    pub fn buffer_len(&self) -> usize {
+        self.buffer_len
+    }
+
+    /// Takes a buffer from the pool, allocating a new one if none are
+    /// available.
+    pub fn get(&self) -> PooledBuffer<'_> {
+        let buf = self
+            .buffers
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .pop()
+            .unwrap_or_else(|| vec![0u8; self.buffer_len].into_boxed_slice());
+
+        PooledBuffer { pool: self, buf }
+    }
+}
+
+impl ops::Deref for PooledBuffer<'_> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl ops::DerefMut for PooledBuffer<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        let buf = mem::take(&mut self.buf);
+
+        self.pool
+            .buffers
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(buf);
+    }
+}
+
+impl fmt::Debug for Utf8BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Utf8BufferPool")
+            .field("buffer_len", &self.buffer_len)
+            .finish()
+    }
+}
+
+impl fmt::Debug for PooledBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PooledBuffer")
+    }
+}
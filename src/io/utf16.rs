@@ -0,0 +1,272 @@
+use std::{error, fmt, io};
+
+/// minimum buffer length that can hold any UTF-16 char
+const MIN_BUF_LEN: usize = 4;
+
+/// The byte order of a UTF-16 stream.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endianness {
+    /// The most significant byte of each code unit comes first.
+    Big,
+    /// The least significant byte of each code unit comes first.
+    Little,
+}
+
+/// Reads UTF-16 data from an [`io::Read`] implementation and transcodes it
+/// into chunks of valid UTF-8 characters, using a temporary storage buffer to
+/// minimize read calls.
+///
+/// If the stream starts with a byte order mark (BOM), it is skipped and
+/// determines the byte order of the stream. Otherwise, the byte order given
+/// to [`Utf16ChunkReader::new`] is used.
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::io::{Endianness, Utf16ChunkReader};
+/// # fn main() -> std::io::Result<()> {
+/// // "h😀" in UTF-16BE, preceded by a BOM
+/// let bytes: &[u8] = &[0xFE, 0xFF, 0x00, 0x68, 0xD8, 0x3D, 0xDE, 0x00];
+/// let mut buf = [0u8; 4];
+/// let mut reader = Utf16ChunkReader::new(&mut buf, bytes, Endianness::Little);
+/// let mut out = String::new();
+///
+/// while reader.read_chunk()? {
+///     out.push_str(reader.chunk());
+/// }
+///
+/// assert_eq!(out, "h😀");
+/// assert_eq!(reader.endianness(), Endianness::Big);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Performance
+///
+/// Each chunk is transcoded into a [`String`] owned by the reader, which is
+/// allocated once with enough capacity for any chunk that fits in the buffer.
+pub struct Utf16ChunkReader<'buf, Inner> {
+    inner: Inner,
+    buf: &'buf mut [u8],
+    /// number of bytes in `buf`
+    len: usize,
+    /// the last read chunk, transcoded to UTF-8
+    chunk: String,
+    /// byte order of the stream
+    endianness: Endianness,
+    /// whether the start of the stream has been checked for a BOM
+    bom_checked: bool,
+    /// byte offset of the start of `buf` in the stream
+    offset: u64,
+}
+
+/// The error wrapped in an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`] when a stream does not contain valid UTF-16.
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::io::{Endianness, InvalidUtf16Error, Utf16ChunkReader};
+/// // "ok" followed by a lone low surrogate in UTF-16LE
+/// let bytes: &[u8] = &[0x6F, 0x00, 0x6B, 0x00, 0x00, 0xDC];
+/// let mut buf = [0u8; 16];
+/// let mut reader = Utf16ChunkReader::new(&mut buf, bytes, Endianness::Little);
+///
+/// assert!(reader.read_chunk().unwrap());
+///
+/// let err = reader.read_chunk().unwrap_err();
+/// let details = err.get_ref().unwrap().downcast_ref::<InvalidUtf16Error>().unwrap();
+///
+/// assert_eq!(details.offset(), 4);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidUtf16Error {
+    offset: u64,
+}
+
+impl<'buf, Inner> Utf16ChunkReader<'buf, Inner>
+where
+    Inner: io::Read,
+{
+    /// Creates a new reader that reads from `inner` using `buf` as temporary
+    /// storage.
+    ///
+    /// The given `endianness` is used unless the stream starts with a BOM. The
+    /// buffer must be at least 4 bytes long so that it can hold any UTF-16
+    /// character. This is checked with a debug assertion.
+    pub fn new(buf: &'buf mut [u8], inner: Inner, endianness: Endianness) -> Self {
+        debug_assert!(
+            buf.len() >= MIN_BUF_LEN,
+            "buffer must be at least {MIN_BUF_LEN} bytes long"
+        );
+
+        // each 2-byte code unit is at most 3 bytes of UTF-8, and each 4-byte
+        // surrogate pair is 4 bytes of UTF-8
+        let chunk = String::with_capacity(buf.len() / 2 * 3);
+
+        Self {
+            inner,
+            buf,
+            len: 0,
+            chunk,
+            endianness,
+            bom_checked: false,
+            offset: 0,
+        }
+    }
+
+    /// Gets the last read chunk of valid characters.
+    ///
+    /// Returns `""` if no chunk has been read yet or an error has occured;
+    /// otherwise, the return value is always a non-empty string.
+    #[inline]
+    pub fn chunk(&self) -> &str {
+        &self.chunk
+    }
+
+    /// Removes `amt` bytes from the start of the buffer.
+    fn consume_bytes(&mut self, amt: usize) {
+        self.buf.copy_within(amt..self.len, 0);
+        self.len -= amt;
+        self.offset += amt as u64;
+    }
+
+    /// Gets the byte order of the stream.
+    ///
+    /// This is the byte order given to [`Utf16ChunkReader::new`] unless the
+    /// stream started with a BOM, which is known after the first chunk is
+    /// read.
+    #[inline]
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Reads the next chunk of valid characters.
+    ///
+    /// This method reads until the buffer is full or the end of the stream is
+    /// reached. Returns `false` if there is no data to read. Surrogate pairs and
+    /// code units split across reads are carried over to the next chunk.
+    pub fn read_chunk(&mut self) -> io::Result<bool> {
+        self.chunk.clear();
+
+        let mut eof = self.read_to_fill()?;
+
+        if !self.bom_checked {
+            self.bom_checked = true;
+
+            let bom_endianness = match self.buf[..self.len] {
+                [0xFE, 0xFF, ..] => Some(Endianness::Big),
+                [0xFF, 0xFE, ..] => Some(Endianness::Little),
+                _ => None,
+            };
+
+            if let Some(endianness) = bom_endianness {
+                self.endianness = endianness;
+                self.consume_bytes(2);
+
+                // make room for a complete char after the BOM
+                eof = self.read_to_fill()?;
+            }
+        }
+
+        // transcode as many complete chars as possible
+
+        let mut pos = 0;
+
+        while pos + 2 <= self.len {
+            let unit = self.unit_at(pos);
+
+            let (result, unit_len) = match unit {
+                0xD800..=0xDBFF if pos + 4 <= self.len => {
+                    let low = self.unit_at(pos + 2);
+
+                    (char::decode_utf16([unit, low]).next(), 4)
+                }
+
+                // wait for the rest of the surrogate pair unless there is no
+                // more data
+                0xD800..=0xDBFF if !eof => break,
+
+                _ => (char::decode_utf16([unit]).next(), 2),
+            };
+
+            match result {
+                Some(Ok(ch)) => self.chunk.push(ch),
+                _ => break,
+            }
+
+            pos += unit_len;
+        }
+
+        self.consume_bytes(pos);
+
+        if self.chunk.is_empty() {
+            if self.len == 0 {
+                return Ok(false);
+            }
+
+            // the buffer can always hold a complete char, so if none were
+            // transcoded, the data at the start of the buffer is invalid
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                InvalidUtf16Error {
+                    offset: self.offset,
+                },
+            ));
+        }
+
+        Ok(true)
+    }
+
+    /// Reads until the buffer is full or the end of the stream is reached, and
+    /// returns `true` if the end of the stream was reached.
+    fn read_to_fill(&mut self) -> io::Result<bool> {
+        while self.len != self.buf.len() {
+            match self.inner.read(&mut self.buf[self.len..]) {
+                Ok(0) => return Ok(true),
+                Ok(n) => self.len += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Gets the code unit at a byte offset in the buffer.
+    fn unit_at(&self, pos: usize) -> u16 {
+        let bytes = [self.buf[pos], self.buf[pos + 1]];
+
+        match self.endianness {
+            Endianness::Big => u16::from_be_bytes(bytes),
+            Endianness::Little => u16::from_le_bytes(bytes),
+        }
+    }
+}
+
+impl InvalidUtf16Error {
+    /// Gets the byte offset in the stream of the first code unit that is not
+    /// valid UTF-16.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl error::Error for InvalidUtf16Error {}
+
+impl fmt::Display for InvalidUtf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stream did not contain valid UTF-16: invalid code unit at offset {}",
+            self.offset
+        )
+    }
+}
+
+impl<Inner> fmt::Debug for Utf16ChunkReader<'_, Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Utf16ChunkReader")
+    }
+}
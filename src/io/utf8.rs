@@ -1,4 +1,7 @@
-use std::{fmt, io, mem, ptr, str};
+use std::{error, fmt, io, mem, ptr, str};
+
+/// minimum buffer length that can hold any UTF-8 char
+const MIN_BUF_LEN: usize = 4;
 
 /// Reads UTF-8 data from an [`io::Read`] implementation character-by-character,
 /// using a temporary storage buffer to minimize read calls.
@@ -6,13 +9,18 @@ use std::{fmt, io, mem, ptr, str};
 /// # Example
 ///
 /// ```no_run
-/// let file = File::open("example.txt");
+/// # use std::fs::File;
+/// # use lexlib::io::Utf8CharReader;
+/// # fn main() -> std::io::Result<()> {
+/// let file = File::open("example.txt")?;
 /// let mut buf = vec![0u8; 8192];
 /// let mut reader = Utf8CharReader::new(&mut buf, file);
 ///
 /// while let Some(ch) = reader.read_char()? {
 ///     print!("{}", ch);
 /// }
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Performance
@@ -25,6 +33,9 @@ use std::{fmt, io, mem, ptr, str};
 pub struct Utf8CharReader<'buf, Inner> {
     reader: Utf8ChunkReader<'buf, Inner>,
     iter: str::Chars<'buf>,
+    line: usize,
+    column: usize,
+    position: u64,
 }
 
 /// Reads chunks of valid UTF-8 characters from an [`io::Read`] implementation,
@@ -33,13 +44,42 @@ pub struct Utf8CharReader<'buf, Inner> {
 /// # Example
 ///
 /// ```no_run
-/// let file = File::open("example.txt");
+/// # use std::fs::File;
+/// # use lexlib::io::Utf8ChunkReader;
+/// # fn main() -> std::io::Result<()> {
+/// let file = File::open("example.txt")?;
 /// let mut buf = vec![0u8; 8192];
 /// let mut reader = Utf8ChunkReader::new(&mut buf, file);
 ///
 /// while reader.read_chunk()? {
 ///     print!("{}", reader.chunk());
 /// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Chained inputs
+///
+/// Multiple inputs can be read as one logical stream with [`io::Read::chain`].
+/// Incomplete characters at the end of each chunk are carried over to the next
+/// one, so a character may be split across the boundary between two inputs:
+///
+/// ```
+/// # use std::io::Read;
+/// # use lexlib::io::Utf8ChunkReader;
+/// # fn main() -> std::io::Result<()> {
+/// let first: &[u8] = b"caf\xC3";
+/// let second: &[u8] = b"\xA9!";
+/// let mut buf = [0u8; 4];
+/// let mut reader = Utf8ChunkReader::new(&mut buf, first.chain(second));
+///
+/// assert!(reader.read_chunk()?);
+/// assert_eq!(reader.chunk(), "caf");
+/// assert!(reader.read_chunk()?);
+/// assert_eq!(reader.chunk(), "é!");
+/// assert!(!reader.read_chunk()?);
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Performance
@@ -55,24 +95,132 @@ pub struct Utf8ChunkReader<'buf, Inner> {
     len: usize,
     /// number of bytes in `buf` that represent full, valid UTF-8 chars
     len_utf8: usize,
+    /// number of bytes at the start of the chunk marked as consumed
+    consumed: usize,
+    /// byte offset of the start of `buf` in the stream
+    offset: u64,
+    /// maximum number of bytes in a chunk
+    max_chunk_len: usize,
+}
+
+/// The error wrapped in an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`] when a stream does not contain valid UTF-8.
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::io::{InvalidUtf8Error, Utf8ChunkReader};
+/// let mut buf = [0u8; 16];
+/// let mut reader = Utf8ChunkReader::new(&mut buf, &b"ok\xFF"[..]);
+///
+/// assert!(reader.read_chunk().unwrap());
+///
+/// let err = reader.read_chunk().unwrap_err();
+/// let details = err.get_ref().unwrap().downcast_ref::<InvalidUtf8Error>().unwrap();
+///
+/// assert_eq!(details.offset(), 2);
+/// assert_eq!(details.byte(), 0xFF);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidUtf8Error {
+    offset: u64,
+    byte: u8,
+}
+
+/// Reads an entire UTF-8 stream into a [`String`].
+///
+/// Unlike [`io::Read::read_to_string`], if the stream is not valid UTF-8, this
+/// function returns `Ok(Err((prefix, offset)))`, where `prefix` contains all of
+/// the valid UTF-8 that was read and `offset` is the byte offset of the first
+/// invalid byte in the stream. Other I/O errors are returned as `Err`.
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::io::read_utf8_checked;
+/// # fn main() -> std::io::Result<()> {
+/// let result = read_utf8_checked(&b"valid\xFFinvalid"[..])?;
+///
+/// assert_eq!(result, Err(("valid".to_string(), 5)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_utf8_checked(reader: impl io::Read) -> io::Result<Result<String, (String, u64)>> {
+    let mut buf = vec![0u8; 8192];
+    let mut reader = Utf8ChunkReader::new(&mut buf, reader);
+    let mut out = String::new();
+
+    loop {
+        match reader.read_chunk_into(&mut out) {
+            Ok(true) => continue,
+            Ok(false) => return Ok(Ok(out)),
+            Err(err) => {
+                let Some(details) = err
+                    .get_ref()
+                    .and_then(|inner| inner.downcast_ref::<InvalidUtf8Error>())
+                else {
+                    return Err(err);
+                };
+
+                return Ok(Err((out, details.offset)));
+            }
+        }
+    }
 }
 
 impl<'buf, Inner> Utf8CharReader<'buf, Inner>
 where
     Inner: io::Read,
 {
+    /// Creates a new reader that reads from `inner` using `buf` as temporary
+    /// storage.
+    ///
+    /// The buffer must be at least 4 bytes long so that it can hold any UTF-8
+    /// character. This is checked with a debug assertion.
     #[inline]
     pub fn new(buf: &'buf mut [u8], inner: Inner) -> Self {
         Self {
             reader: Utf8ChunkReader::new(buf, inner),
             iter: "".chars(),
+            line: 1,
+            column: 1,
+            position: 0,
         }
     }
 
-    /// Reads the next valid [`char`].
+    /// Updates the line, column, and position after reading a [`char`] that
+    /// was `len` bytes long in the stream.
+    fn advance(&mut self, ch: char, len: usize) {
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        self.position += len as u64;
+    }
+
+    /// Gets the current column number.
     ///
-    /// Returns [`None`] if there is no data to read.
-    pub fn read_char(&mut self) -> io::Result<Option<char>> {
+    /// This is the number of code points read since the beginning of the line,
+    /// starting from 1.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Gets the current line number.
+    ///
+    /// This is the number of newline characters read since the beginning of
+    /// the stream, starting from 1.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Reads the next valid [`char`] without updating the line and column.
+    fn next_char(&mut self) -> io::Result<Option<char>> {
         if let Some(ch) = self.iter.next() {
             return Ok(Some(ch));
         }
@@ -93,19 +241,130 @@ where
             })
         }
     }
+
+    /// Gets the current position in the stream.
+    ///
+    /// This is the number of bytes of the stream that have been read as
+    /// characters, which is the byte offset of the next character.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next valid [`char`].
+    ///
+    /// Returns [`None`] if there is no data to read.
+    pub fn read_char(&mut self) -> io::Result<Option<char>> {
+        let Some(ch) = self.next_char()? else {
+            return Ok(None);
+        };
+
+        self.advance(ch, ch.len_utf8());
+
+        Ok(Some(ch))
+    }
+
+    /// Reads the next [`char`], replacing invalid UTF-8 sequences with
+    /// [`char::REPLACEMENT_CHARACTER`] (U+FFFD).
+    ///
+    /// Each invalid sequence is skipped and replaced with a single replacement
+    /// character, the same way as [`String::from_utf8_lossy`]. Returns [`None`]
+    /// if there is no data to read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::io::Utf8CharReader;
+    /// # fn main() -> std::io::Result<()> {
+    /// let input = b"a\xE2\x82b\xFFc\xF0\x9F";
+    /// let mut buf = [0u8; 4];
+    /// let mut reader = Utf8CharReader::new(&mut buf, &input[..]);
+    /// let mut out = String::new();
+    ///
+    /// while let Some(ch) = reader.read_char_lossy()? {
+    ///     out.push(ch);
+    /// }
+    ///
+    /// assert_eq!(out, String::from_utf8_lossy(input));
+    /// assert_eq!(reader.position(), input.len() as u64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_char_lossy(&mut self) -> io::Result<Option<char>> {
+        let (ch, len) = match self.next_char() {
+            Ok(Some(ch)) => (ch, ch.len_utf8()),
+            Ok(None) => return Ok(None),
+
+            Err(err) if is_invalid_utf8(&err) => {
+                (char::REPLACEMENT_CHARACTER, self.reader.skip_invalid())
+            }
+
+            Err(err) => return Err(err),
+        };
+
+        self.advance(ch, len);
+
+        Ok(Some(ch))
+    }
+
+    /// Reads the next valid [`char`] along with its byte offset in the stream.
+    ///
+    /// Returns [`None`] if there is no data to read.
+    #[inline]
+    pub fn read_char_indexed(&mut self) -> io::Result<Option<(char, u64)>> {
+        let offset = self.position;
+
+        Ok(self.read_char()?.map(|ch| (ch, offset)))
+    }
 }
 
 impl<'buf, Inner> Utf8ChunkReader<'buf, Inner>
 where
     Inner: io::Read,
 {
+    /// Creates a new reader that reads from `inner` using `buf` as temporary
+    /// storage.
+    ///
+    /// The buffer must be at least 4 bytes long so that it can hold any UTF-8
+    /// character. This is checked with a debug assertion.
     #[inline]
     pub fn new(buf: &'buf mut [u8], inner: Inner) -> Self {
+        debug_assert!(
+            buf.len() >= MIN_BUF_LEN,
+            "buffer must be at least {MIN_BUF_LEN} bytes long"
+        );
+
         Self {
             inner,
             buf,
             len: 0,
             len_utf8: 0,
+            consumed: 0,
+            offset: 0,
+            max_chunk_len: usize::MAX,
+        }
+    }
+
+    /// Creates a new reader that reads `prefix` followed by the data in
+    /// `inner`, using `buf` as temporary storage.
+    ///
+    /// This is useful when some bytes have already been read from `inner`, for
+    /// example to detect the format of a stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` is longer than `buf`.
+    pub fn with_prefill(buf: &'buf mut [u8], prefix: &[u8], inner: Inner) -> Self {
+        assert!(
+            prefix.len() <= buf.len(),
+            "prefix must not be longer than the buffer"
+        );
+
+        buf[..prefix.len()].copy_from_slice(prefix);
+
+        Self {
+            len: prefix.len(),
+            ..Self::new(buf, inner)
         }
     }
 
@@ -118,43 +377,301 @@ where
         unsafe { str::from_utf8_unchecked(self.buf.get_unchecked(..self.len_utf8)) }
     }
 
+    /// Marks `amt` bytes at the start of the string returned by
+    /// [`Utf8ChunkReader::fill_buf`] as consumed, so they are not returned by
+    /// the next call.
+    ///
+    /// The given `amt` must fall on a char boundary and must not exceed the
+    /// length of the string returned by `fill_buf`.
+    #[inline]
+    pub fn consume(&mut self, amt: usize) {
+        debug_assert!(
+            self.chunk()[self.consumed..].is_char_boundary(amt),
+            "consumed amount must be a char boundary within the chunk"
+        );
+
+        self.consumed = (self.consumed + amt).min(self.len_utf8);
+    }
+
+    /// Returns the unconsumed part of the current chunk, reading the next chunk
+    /// if the current one has been entirely consumed.
+    ///
+    /// Returns `""` if there is no data to read. Use
+    /// [`Utf8ChunkReader::consume`] to mark part of the returned string as
+    /// consumed.
+    pub fn fill_buf(&mut self) -> io::Result<&str> {
+        if self.consumed == self.len_utf8 && !self.read_chunk_inner(true)? {
+            return Ok("");
+        }
+
+        Ok(unsafe { self.chunk().get_unchecked(self.consumed..) })
+    }
+
+    /// Reads each remaining chunk of valid UTF-8 characters and passes it to a
+    /// function.
+    ///
+    /// This method is used instead of an [`Iterator`] because each chunk
+    /// borrows the buffer, which is overwritten when the next chunk is read.
+    /// Returns the first error that occurs, if any.
+    pub fn for_each_chunk(&mut self, mut f: impl FnMut(&str)) -> io::Result<()> {
+        while self.read_chunk()? {
+            f(self.chunk());
+        }
+
+        Ok(())
+    }
+
+    /// Gets the maximum number of bytes in a chunk, as set by
+    /// [`Utf8ChunkReader::set_max_chunk_len`].
+    #[inline]
+    pub fn max_chunk_len(&self) -> usize {
+        self.max_chunk_len
+    }
+
     /// Reads the next chunk of valid UTF-8 characters.
     ///
-    /// Returns `false` if there is no data to read.
+    /// This method reads until the buffer is full or the end of the stream is
+    /// reached. Returns `false` if there is no data to read. Any part of the
+    /// previous chunk that was not marked with [`Utf8ChunkReader::consume`] is
+    /// returned again at the start of the next chunk.
+    #[inline]
     pub fn read_chunk(&mut self) -> io::Result<bool> {
+        let result = self.read_chunk_inner(true)?;
+
+        // the entire chunk is returned to the caller
+        self.consumed = self.len_utf8;
+
+        Ok(result)
+    }
+
+    /// Reads the next chunk of valid UTF-8 characters, returning as soon as any
+    /// are available.
+    ///
+    /// Unlike [`Utf8ChunkReader::read_chunk`], this method does not wait for
+    /// the buffer to fill, which is useful for interactive streams that deliver
+    /// data slowly. It only reads more than once if the data read so far ends
+    /// in the middle of a character.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::io::{self, Read};
+    /// # use lexlib::io::Utf8ChunkReader;
+    /// # fn main() -> io::Result<()> {
+    /// // delivers one byte per read, like a slow interactive stream
+    /// struct Slow<'a>(&'a [u8]);
+    ///
+    /// impl Read for Slow<'_> {
+    ///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    ///         (&mut self.0).take(1).read(buf)
+    ///     }
+    /// }
+    ///
+    /// let input = "añ€😀";
+    /// let mut buf = [0u8; 16];
+    /// let mut reader = Utf8ChunkReader::new(&mut buf, Slow(input.as_bytes()));
+    /// let mut out = String::new();
+    ///
+    /// while reader.read_chunk_available()? {
+    ///     // each chunk is returned as soon as one complete char is available
+    ///     assert_eq!(reader.chunk().chars().count(), 1);
+    ///
+    ///     out.push_str(reader.chunk());
+    /// }
+    ///
+    /// assert_eq!(out, input);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn read_chunk_available(&mut self) -> io::Result<bool> {
+        let result = self.read_chunk_inner(false)?;
+
+        // the entire chunk is returned to the caller
+        self.consumed = self.len_utf8;
+
+        Ok(result)
+    }
+
+    /// Reads the next chunk of valid UTF-8 characters and appends it to `out`.
+    ///
+    /// This is equivalent to [`Utf8ChunkReader::read_chunk`] followed by
+    /// appending [`Utf8ChunkReader::chunk`] to `out`. Returns `false` if there
+    /// is no data to read.
+    #[inline]
+    pub fn read_chunk_into(&mut self, out: &mut String) -> io::Result<bool> {
+        if !self.read_chunk()? {
+            return Ok(false);
+        }
+
+        out.push_str(self.chunk());
+
+        Ok(true)
+    }
+
+    /// Reads the next chunk of valid UTF-8 characters, either until the buffer
+    /// is full or until at least one character is available.
+    fn read_chunk_inner(&mut self, fill: bool) -> io::Result<bool> {
         unsafe {
             // reset the buffer
 
             let buf_ptr = self.buf.as_mut_ptr();
-            let tail_ptr = buf_ptr.add(self.len_utf8);
-            let tail_len = self.len - self.len_utf8;
+            let tail_ptr = buf_ptr.add(self.consumed);
+            let tail_len = self.len - self.consumed;
 
-            // copies any dangling invalid/incomplete UTF-8 chars to the front
-            // of the buf
+            // copies any unconsumed chars and dangling invalid/incomplete UTF-8
+            // chars to the front of the buf
             ptr::copy(tail_ptr, buf_ptr, tail_len);
 
+            self.offset += self.consumed as u64;
             self.len = tail_len;
             self.len_utf8 = 0;
+            self.consumed = 0;
+
+            let mut eof = false;
+
+            // if not filling, return any valid chars deferred from the previous
+            // chunk without waiting for another read
+
+            if !fill {
+                self.validate();
+            }
 
-            // read until the buffer is full
+            while self.len_utf8 == 0 {
+                // read until the buffer is full, or only once if not filling
 
-            while self.len != self.buf.len() {
-                match self.inner.read(self.buf.get_unchecked_mut(self.len..)) {
-                    Ok(0) => break,
-                    Ok(n) => self.len += n,
-                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
-                    Err(err) => return Err(err),
+                while self.len != self.buf.len() {
+                    match self.inner.read(self.buf.get_unchecked_mut(self.len..)) {
+                        Ok(0) => {
+                            eof = true;
+                            break;
+                        }
+
+                        Ok(n) => {
+                            self.len += n;
+
+                            if !fill {
+                                break;
+                            }
+                        }
+
+                        Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                if self.len == 0 {
+                    return Ok(false);
+                }
+
+                self.validate();
+
+                // if not filling, keep reading only when the buffer contains
+                // nothing but the start of an incomplete char
+
+                if fill || eof || self.len_utf8 != 0 || self.len == self.buf.len() {
+                    break;
+                }
+
+                let incomplete = str::from_utf8(self.buf.get_unchecked(..self.len))
+                    .is_err_and(|err| err.error_len().is_none());
+
+                if !incomplete {
+                    break;
                 }
             }
 
-            if self.len == 0 {
-                return Ok(false);
+            // defer any valid chars past the maximum chunk length to the next
+            // chunk, but always return at least one char
+
+            if self.len_utf8 > self.max_chunk_len {
+                let valid = str::from_utf8_unchecked(self.buf.get_unchecked(..self.len_utf8));
+
+                self.len_utf8 = match valid.floor_char_boundary(self.max_chunk_len) {
+                    0 => valid.chars().next().unwrap_unchecked().len_utf8(),
+                    len => len,
+                };
             }
+        }
+
+        if self.len_utf8 == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                InvalidUtf8Error {
+                    offset: self.offset,
+                    byte: self.buf[0],
+                },
+            ));
+        }
+
+        Ok(true)
+    }
+
+    /// Sets the maximum number of bytes in a chunk.
+    ///
+    /// Any valid characters past this length are kept in the buffer and
+    /// returned in the next chunk, which bounds the amount of text processed
+    /// at once regardless of the buffer size. Chunks are shortened to the
+    /// nearest char boundary, but always contain at least one character, so a
+    /// chunk may exceed a maximum of fewer than 4 bytes. By default, there is
+    /// no maximum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::io::Utf8ChunkReader;
+    /// let mut buf = [0u8; 16];
+    /// let mut reader = Utf8ChunkReader::new(&mut buf, "añb".as_bytes());
+    ///
+    /// reader.set_max_chunk_len(2);
+    ///
+    /// assert!(reader.read_chunk().unwrap());
+    /// assert_eq!(reader.chunk(), "a");
+    ///
+    /// assert!(reader.read_chunk().unwrap());
+    /// assert_eq!(reader.chunk(), "ñ");
+    ///
+    /// assert!(reader.read_chunk().unwrap());
+    /// assert_eq!(reader.chunk(), "b");
+    /// ```
+    #[inline]
+    pub fn set_max_chunk_len(&mut self, max_len: usize) {
+        self.max_chunk_len = max_len;
+    }
 
-            // validate utf8 bytes
+    /// Removes the invalid UTF-8 sequence at the start of the buffer after
+    /// [`Utf8ChunkReader::read_chunk`] has returned an [`InvalidUtf8Error`].
+    ///
+    /// Returns the number of bytes removed, which is always at least 1.
+    fn skip_invalid(&mut self) -> usize {
+        debug_assert!(self.len_utf8 == 0 && self.len > 0);
+
+        let invalid = &self.buf[..self.len];
+
+        // an incomplete sequence is only invalid at the end of the stream, so
+        // it can be skipped entirely
+        let invalid_len = match str::from_utf8(invalid) {
+            Ok(_) => 0,
+            Err(err) => err.error_len().unwrap_or(invalid.len()),
+        };
+
+        self.buf.copy_within(invalid_len..self.len, 0);
+        self.len -= invalid_len;
+        self.offset += invalid_len as u64;
 
-            self.len_utf8 = self
-                .buf
+        invalid_len
+    }
+
+    /// Sets the number of bytes at the start of the buffer that represent full,
+    /// valid UTF-8 chars.
+    fn validate(&mut self) {
+        if self.len == 0 {
+            self.len_utf8 = 0;
+            return;
+        }
+
+        self.len_utf8 = unsafe {
+            self.buf
                 // len is always > 0 and <= buf.len()
                 .get_unchecked(..self.len)
                 .utf8_chunks()
@@ -163,17 +680,105 @@ where
                 // slice is non-empty
                 .unwrap_unchecked()
                 .valid()
-                .len();
-        }
+                .len()
+        };
+    }
+}
 
-        if self.len_utf8 == 0 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "stream did not contain valid UTF-8",
-            ));
+impl<Inner> Utf8ChunkReader<'_, Inner>
+where
+    Inner: io::Read + io::Seek,
+{
+    /// Seeks relative to the current logical position in the stream.
+    ///
+    /// The logical position is the end of the last chunk returned by
+    /// [`Utf8ChunkReader::read_chunk`], or the end of the part of the chunk
+    /// marked with [`Utf8ChunkReader::consume`]. This accounts for data that
+    /// has been read into the buffer but not returned, unlike seeking the
+    /// inner reader directly.
+    ///
+    /// If the new position is within the current chunk, no seek is performed
+    /// and the buffered data is read again. Otherwise, the buffer is discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use lexlib::io::Utf8ChunkReader;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut buf = [0u8; 4];
+    /// let mut reader = Utf8ChunkReader::new(&mut buf, Cursor::new("abcdefghij"));
+    ///
+    /// assert!(reader.read_chunk()?);
+    /// assert_eq!(reader.chunk(), "abcd");
+    ///
+    /// reader.seek_relative(-2)?;
+    ///
+    /// assert!(reader.read_chunk()?);
+    /// assert_eq!(reader.chunk(), "cdef");
+    ///
+    /// reader.seek_relative(-6)?;
+    ///
+    /// assert!(reader.read_chunk()?);
+    /// assert_eq!(reader.chunk(), "abcd");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        let target = self.consumed as i64 + offset;
+
+        if target >= 0 && self.chunk().is_char_boundary(target as usize) {
+            self.consumed = target as usize;
+
+            return Ok(());
         }
 
-        Ok(true)
+        // the inner reader is at the end of the buffer, not the logical
+        // position
+        let unconsumed = (self.len - self.consumed) as i64;
+
+        self.inner
+            .seek(io::SeekFrom::Current(offset - unconsumed))?;
+
+        self.offset = self.offset.saturating_add_signed(target);
+        self.len = 0;
+        self.len_utf8 = 0;
+        self.consumed = 0;
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if an error wraps an [`InvalidUtf8Error`].
+fn is_invalid_utf8(err: &io::Error) -> bool {
+    err.get_ref()
+        .is_some_and(|inner| inner.is::<InvalidUtf8Error>())
+}
+
+impl InvalidUtf8Error {
+    /// Gets the value of the first byte that is not valid UTF-8.
+    #[inline]
+    pub fn byte(&self) -> u8 {
+        self.byte
+    }
+
+    /// Gets the byte offset in the stream of the first byte that is not valid
+    /// UTF-8.
+    #[inline]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl error::Error for InvalidUtf8Error {}
+
+impl fmt::Display for InvalidUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stream did not contain valid UTF-8: invalid byte {:#04x} at offset {}",
+            self.byte, self.offset
+        )
     }
 }
 
@@ -1,3 +1,11 @@
+mod char_class;
+#[cfg(feature = "serde")]
+mod de;
+mod scan_error;
 mod scanner;
 
-pub use self::scanner::Scanner;
+pub use self::char_class::CharClass;
+#[cfg(feature = "serde")]
+pub use self::de::{DeserializeError, ScannerDeserializer};
+pub use self::scan_error::{ScanError, ScanErrorKind};
+pub use self::scanner::{Checkpoint, Location, Scanner};
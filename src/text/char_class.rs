@@ -0,0 +1,95 @@
+use std::{fmt, ops::RangeInclusive};
+
+/// A reusable set of characters, built from individual characters, ranges, and
+/// predicates.
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::text::{CharClass, Scanner};
+/// let ident = CharClass::new()
+///     .with_range('a'..='z')
+///     .with_range('A'..='Z')
+///     .with_char('_')
+///     .with_predicate(char::is_alphabetic);
+///
+/// let mut scanner = Scanner::new("snake_case_ünïcode = 1");
+///
+/// assert_eq!(scanner.take_while_class(&ident), "snake_case_ünïcode");
+/// ```
+///
+/// # Performance
+///
+/// Membership of ASCII characters is precomputed when the class is built, so
+/// checking an ASCII character is a single bit test. Other characters are
+/// checked against each range and predicate in turn.
+#[derive(Clone, Default)]
+pub struct CharClass {
+    /// bitset of the ASCII chars in the class
+    ascii: u128,
+    /// ranges of non-ASCII chars in the class
+    ranges: Vec<RangeInclusive<char>>,
+    /// predicates for non-ASCII chars in the class
+    predicates: Vec<fn(char) -> bool>,
+}
+
+impl CharClass {
+    /// Creates an empty character class.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the class contains the given character.
+    #[inline]
+    pub fn contains(&self, ch: char) -> bool {
+        if ch.is_ascii() {
+            return self.ascii & (1 << ch as u32) != 0;
+        }
+
+        self.ranges.iter().any(|range| range.contains(&ch))
+            || self.predicates.iter().any(|predicate| predicate(ch))
+    }
+
+    /// Adds a single character to the class.
+    pub fn with_char(self, ch: char) -> Self {
+        self.with_range(ch..=ch)
+    }
+
+    /// Adds every character that satisfies a predicate to the class.
+    pub fn with_predicate(mut self, predicate: fn(char) -> bool) -> Self {
+        for byte in 0..128u8 {
+            if predicate(byte as char) {
+                self.ascii |= 1 << byte;
+            }
+        }
+
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Adds a range of characters to the class.
+    pub fn with_range(mut self, range: RangeInclusive<char>) -> Self {
+        for byte in 0..128u8 {
+            if range.contains(&(byte as char)) {
+                self.ascii |= 1 << byte;
+            }
+        }
+
+        if !range.end().is_ascii() {
+            self.ranges.push(range);
+        }
+
+        self
+    }
+}
+
+impl fmt::Debug for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CharClass")
+            .field("ascii", &format_args!("{:#034x}", self.ascii))
+            .field("ranges", &self.ranges)
+            .field("predicates", &self.predicates.len())
+            .finish()
+    }
+}
@@ -0,0 +1,452 @@
+use super::{Location, Scanner};
+use serde::de::{self, IntoDeserializer};
+use std::{borrow::Cow, error, fmt, sync::Arc};
+
+/// A [`serde::Deserializer`] that parses simple self-describing tokens from
+/// text using a [`Scanner`].
+///
+/// The supported syntax is:
+///
+/// * Numbers such as `42`, `-7`, and `3.5e2`.
+/// * Booleans `true` and `false`, and `null` for missing optional values.
+/// * Quoted strings such as `"a \"quoted\" string"`, with the escape sequences
+///   `\"`, `\\`, `\n`, `\r`, and `\t`.
+/// * Bare identifiers such as `name`, which are deserialized as strings.
+/// * Sequences such as `[1, 2, 3]`.
+/// * Maps and structs such as `{ name: "lexlib", version: 1 }`.
+///
+/// Errors report the line and column in the source string where they occured,
+/// and the file name if the scanner was created with
+/// [`Scanner::with_file_name`].
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::text::ScannerDeserializer;
+/// # use serde::Deserialize;
+/// #[derive(Debug, Deserialize, PartialEq)]
+/// struct Package {
+///     name: String,
+///     version: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// let mut de = ScannerDeserializer::new(r#"{ name: "lexlib", version: 1, tags: [text, io] }"#);
+/// let package = Package::deserialize(&mut de).unwrap();
+///
+/// de.end().unwrap();
+///
+/// assert_eq!(package.name, "lexlib");
+/// assert_eq!(package.tags, ["text", "io"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScannerDeserializer<'src> {
+    scanner: Scanner<'src>,
+}
+
+/// An error that occured while deserializing with a [`ScannerDeserializer`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeserializeError {
+    message: String,
+    location: Option<Location>,
+    file_name: Option<Arc<str>>,
+}
+
+/// Provides access to the elements of a delimited sequence or map.
+struct Delimited<'a, 'src> {
+    de: &'a mut ScannerDeserializer<'src>,
+    close: char,
+    first: bool,
+}
+
+type Result<T> = std::result::Result<T, DeserializeError>;
+
+impl<'src> ScannerDeserializer<'src> {
+    /// Creates a new deserializer that parses the given source string.
+    #[inline]
+    pub fn new(source_str: &'src str) -> Self {
+        Self::from_scanner(Scanner::new(source_str))
+    }
+
+    /// Creates a new deserializer that parses the remaining string of a
+    /// [`Scanner`].
+    #[inline]
+    pub fn from_scanner(scanner: Scanner<'src>) -> Self {
+        Self { scanner }
+    }
+
+    /// Returns an error if anything other than whitespace remains after
+    /// deserializing.
+    pub fn end(&mut self) -> Result<()> {
+        self.scanner.skip_whitespace();
+
+        match self.scanner.peek_char() {
+            Some(ch) => Err(self.error(format_args!("unexpected {ch:?} after value"))),
+            None => Ok(()),
+        }
+    }
+
+    /// Creates an error at the current location.
+    fn error(&self, message: impl fmt::Display) -> DeserializeError {
+        self.error_at(message, self.scanner.location())
+    }
+
+    /// Creates an error at a location.
+    fn error_at(&self, message: impl fmt::Display, location: Location) -> DeserializeError {
+        DeserializeError {
+            message: message.to_string(),
+            location: Some(location),
+            file_name: self.scanner.file_name().map(Arc::from),
+        }
+    }
+
+    /// Consumes the next non-whitespace character if it is equal to an expected
+    /// [`char`] value, or returns an error.
+    fn expect_char(&mut self, expected: char) -> Result<()> {
+        self.scanner.skip_whitespace();
+
+        if self.scanner.take_char_if_eq(expected) {
+            return Ok(());
+        }
+
+        Err(self.unexpected(format_args!("{expected:?}")))
+    }
+
+    /// Consumes an identifier and returns a reference to the slice that
+    /// contains it.
+    fn take_ident(&mut self) -> &'src str {
+        if !self
+            .scanner
+            .peek_char()
+            .is_some_and(unicode_ident::is_xid_start)
+        {
+            return "";
+        }
+
+        self.scanner.take_while(unicode_ident::is_xid_continue)
+    }
+
+    /// Consumes a number and visits it as the narrowest fitting type.
+    fn take_number<'de, V: de::Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value> {
+        let location = self.scanner.location();
+
+        let number = self
+            .scanner
+            .take_while(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.' | '_'));
+
+        let invalid = || self.error_at(format_args!("invalid number {number:?}"), location);
+
+        if number.contains(['.', 'e', 'E']) {
+            visitor.visit_f64(number.parse().map_err(|_| invalid())?)
+        } else if number.starts_with('-') {
+            visitor.visit_i64(number.parse().map_err(|_| invalid())?)
+        } else {
+            visitor.visit_u64(number.parse().map_err(|_| invalid())?)
+        }
+    }
+
+    /// Consumes a quoted string and returns its unescaped contents.
+    fn take_string(&mut self) -> Result<Cow<'src, str>> {
+        self.scanner.skip_whitespace();
+
+        let location = self.scanner.location();
+
+        self.expect_char('"')?;
+
+        let raw = self.scanner.take_until_char_escaped('"', '\\');
+
+        // errors in the string are reported at its opening quote
+
+        if !self.scanner.take_char_if_eq('"') {
+            return Err(self.error_at("unterminated string", location));
+        }
+
+        if !raw.contains('\\') {
+            return Ok(Cow::Borrowed(raw));
+        }
+
+        let mut unescaped = String::with_capacity(raw.len());
+        let mut chars = raw.chars();
+
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                unescaped.push(ch);
+                continue;
+            }
+
+            unescaped.push(match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some(ch @ ('"' | '\\')) => ch,
+                Some(ch) => {
+                    return Err(self.error_at(format_args!("invalid escape \\{ch}"), location));
+                }
+                None => return Err(self.error_at("invalid escape at end of string", location)),
+            });
+        }
+
+        Ok(Cow::Owned(unescaped))
+    }
+
+    /// Consumes a value of any type and visits it.
+    fn take_value<V: de::Visitor<'src>>(&mut self, visitor: V) -> Result<V::Value> {
+        match self.scanner.peek_char() {
+            Some('"') => match self.take_string()? {
+                Cow::Borrowed(string) => visitor.visit_borrowed_str(string),
+                Cow::Owned(string) => visitor.visit_string(string),
+            },
+            Some('0'..='9' | '+' | '-') => self.take_number(visitor),
+
+            Some('[') => {
+                self.scanner.take_char();
+
+                let value = visitor.visit_seq(Delimited::new(self, ']'))?;
+
+                self.expect_char(']')?;
+
+                Ok(value)
+            }
+
+            Some('{') => {
+                self.scanner.take_char();
+
+                let value = visitor.visit_map(Delimited::new(self, '}'))?;
+
+                self.expect_char('}')?;
+
+                Ok(value)
+            }
+
+            _ => match self.take_ident() {
+                "" => Err(self.unexpected("a value")),
+                "true" => visitor.visit_bool(true),
+                "false" => visitor.visit_bool(false),
+                "null" => visitor.visit_unit(),
+                ident => visitor.visit_borrowed_str(ident),
+            },
+        }
+    }
+
+    /// Creates an error for an unexpected character or end of input at the
+    /// current location.
+    fn unexpected(&self, expected: impl fmt::Display) -> DeserializeError {
+        match self.scanner.peek_char() {
+            Some(ch) => self.error(format_args!("expected {expected}, found {ch:?}")),
+            None => self.error(format_args!("expected {expected}, found end of input")),
+        }
+    }
+}
+
+impl DeserializeError {
+    /// Gets the name of the file in which the error occured, if known.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::{Scanner, ScannerDeserializer};
+    /// # use serde::Deserialize;
+    /// let scanner = Scanner::with_file_name("[1, x]", "list.txt");
+    /// let mut de = ScannerDeserializer::from_scanner(scanner);
+    /// let err = Vec::<u32>::deserialize(&mut de).unwrap_err();
+    ///
+    /// assert_eq!(err.file_name(), Some("list.txt"));
+    /// assert_eq!(err.to_string(), "invalid type: string \"x\", expected u32 at list.txt:1:5");
+    /// ```
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Gets the location in the source string where the error occured, if
+    /// known.
+    #[inline]
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    /// Gets the error message, without location information.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets the location and file name of the error if it does not have a
+    /// location yet.
+    fn or_at(mut self, location: Location, file_name: Option<&str>) -> Self {
+        if self.location.is_none() {
+            self.location = Some(location);
+            self.file_name = file_name.map(Arc::from);
+        }
+
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut ScannerDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.scanner.skip_whitespace();
+
+        let location = self.scanner.location();
+
+        self.take_value(visitor)
+            .map_err(|err| err.or_at(location, self.scanner.file_name()))
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.scanner.skip_whitespace();
+
+        let location = self.scanner.location();
+
+        let variant = match self.scanner.peek_char() {
+            Some('"') => self.take_string()?,
+            _ => match self.take_ident() {
+                "" => return Err(self.unexpected("an enum variant")),
+                ident => Cow::Borrowed(ident),
+            },
+        };
+
+        visitor
+            .visit_enum(variant.into_deserializer())
+            .map_err(|err: DeserializeError| err.or_at(location, self.scanner.file_name()))
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.scanner.skip_whitespace();
+
+        let remaining = self.scanner.remaining_str();
+
+        if remaining.starts_with("null")
+            && !remaining[4..]
+                .chars()
+                .next()
+                .is_some_and(unicode_ident::is_xid_continue)
+        {
+            self.take_ident();
+
+            return visitor.visit_none();
+        }
+
+        visitor.visit_some(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'a, 'src> Delimited<'a, 'src> {
+    fn new(de: &'a mut ScannerDeserializer<'src>, close: char) -> Self {
+        Self {
+            de,
+            close,
+            first: true,
+        }
+    }
+
+    /// Consumes the separator before the next element, if any, and returns
+    /// `false` if the closing delimiter is next.
+    fn has_next(&mut self) -> Result<bool> {
+        self.de.scanner.skip_whitespace();
+
+        if self.de.scanner.peek_char() == Some(self.close) {
+            return Ok(false);
+        }
+
+        if !self.first {
+            self.de.expect_char(',')?;
+            self.de.scanner.skip_whitespace();
+
+            // allow a trailing separator
+            if self.de.scanner.peek_char() == Some(self.close) {
+                return Ok(false);
+            }
+        }
+
+        self.first = false;
+
+        Ok(true)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Delimited<'_, 'de> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        self.de.expect_char(':')?;
+
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Delimited<'_, 'de> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            location: None,
+            file_name: None,
+        }
+    }
+}
+
+impl error::Error for DeserializeError {}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.location, &self.file_name) {
+            (Some(location), Some(name)) => write!(
+                f,
+                "{} at {}:{}:{}",
+                self.message, name, location.line, location.column
+            ),
+
+            (Some(location), None) => write!(
+                f,
+                "{} at line {}, column {}",
+                self.message, location.line, location.column
+            ),
+
+            (None, _) => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use super::Location;
+use std::{error, fmt, sync::Arc};
+
+/// An error that occured while scanning a string with a
+/// [`Scanner`][super::Scanner].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScanError {
+    kind: ScanErrorKind,
+    location: Location,
+    file_name: Option<Arc<str>>,
+}
+
+/// The kind of a [`ScanError`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ScanErrorKind {
+    /// A specific character was expected.
+    Expected(char),
+    /// A hexadecimal digit was expected.
+    ExpectedHexDigit,
+    /// An escape sequence was not recognized.
+    InvalidEscape,
+    /// A numeric escape sequence encoded a value that is not a valid code
+    /// point, such as a lone surrogate.
+    InvalidCodePoint(u32),
+    /// A region starting with the given delimiter was not closed before the
+    /// end of the string.
+    Unterminated(char),
+}
+
+impl ScanError {
+    /// Creates a new error of the given kind at a location.
+    #[inline]
+    pub fn new(kind: ScanErrorKind, location: Location) -> Self {
+        Self {
+            kind,
+            location,
+            file_name: None,
+        }
+    }
+
+    /// Gets the name of the file in which the error occured, if known.
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Gets the kind of error.
+    #[inline]
+    pub fn kind(&self) -> ScanErrorKind {
+        self.kind
+    }
+
+    /// Gets the location in the source string where the error occured.
+    #[inline]
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Sets the name of the file in which the error occured.
+    #[inline]
+    pub fn with_file_name(self, name: impl Into<Arc<str>>) -> Self {
+        Self {
+            file_name: Some(name.into()),
+            ..self
+        }
+    }
+}
+
+impl error::Error for ScanError {}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file_name {
+            Some(name) => write!(
+                f,
+                "{} at {}:{}:{}",
+                self.kind, name, self.location.line, self.location.column
+            ),
+
+            None => write!(
+                f,
+                "{} at line {}, column {}",
+                self.kind, self.location.line, self.location.column
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ScanErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Expected(ch) => write!(f, "expected {ch:?}"),
+            Self::ExpectedHexDigit => write!(f, "expected a hexadecimal digit"),
+            Self::InvalidEscape => write!(f, "invalid escape sequence"),
+
+            Self::InvalidCodePoint(value @ 0xD800..=0xDFFF) => {
+                write!(f, "invalid escape of lone surrogate U+{value:04X}")
+            }
+
+            Self::InvalidCodePoint(value) => {
+                write!(f, "invalid escape of out-of-range value {value:#X}")
+            }
+
+            Self::Unterminated(open) => write!(f, "unterminated {open:?}"),
+        }
+    }
+}
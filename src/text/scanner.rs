@@ -1,4 +1,5 @@
-use std::{fmt, slice, str};
+use super::{CharClass, ScanError, ScanErrorKind};
+use std::{fmt, iter, ops::ControlFlow, slice, str, str::FromStr, sync::Arc};
 
 /// A specialized iterator designed for scanning and parsing strings.
 ///
@@ -11,12 +12,52 @@ pub struct Scanner<'src> {
     start: *const u8,
     /// pointer to the current position in the string
     head: *const u8,
+    /// pointer to the start of the current line
+    line_start: *const u8,
     /// the value of the character at `head`, if any
     peek: Option<char>,
     /// the remaining string after the peeked char
     tail: str::Chars<'src>,
     line: usize,
     column: usize,
+    /// name of the file the source string was read from, if any
+    file_name: Option<Arc<str>>,
+    /// whether a byte order mark was skipped with `skip_bom`
+    bom_skipped: bool,
+}
+
+/// A saved state of a [`Scanner`], which can be restored later with
+/// [`Scanner::restore`].
+///
+/// # Example
+///
+/// ```
+/// # use lexlib::text::Scanner;
+/// let mut scanner = Scanner::new("name = value");
+/// let checkpoint = scanner.checkpoint();
+///
+/// scanner.skip_while(|ch| ch != '=');
+///
+/// assert_eq!(scanner.text_since(&checkpoint), "name ");
+///
+/// scanner.restore(&checkpoint);
+///
+/// assert_eq!(scanner.remaining_str(), "name = value");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Checkpoint<'src> {
+    scanner: Scanner<'src>,
+}
+
+/// A location in a source string, as tracked by a [`Scanner`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Location {
+    /// The byte offset from the start of the string.
+    pub position: usize,
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// The column number in code points, starting from 1.
+    pub column: usize,
 }
 
 impl<'src> Scanner<'src> {
@@ -26,10 +67,40 @@ impl<'src> Scanner<'src> {
         Self {
             start: source_str.as_ptr(),
             head: source_str.as_ptr(),
+            line_start: source_str.as_ptr(),
             peek: tail.next(),
             tail,
             line: 1,
             column: 1,
+            file_name: None,
+            bom_skipped: false,
+        }
+    }
+
+    /// Creates a new scanner for a source string that was read from a file
+    /// with the given name.
+    ///
+    /// The file name is included in errors returned by the scanner, so that
+    /// they can be reported as `path:line:column`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// let mut scanner = Scanner::with_file_name("\n(", "example.txt");
+    ///
+    /// scanner.skip_whitespace();
+    ///
+    /// let err = scanner.take_delimited('(', ')').unwrap_err();
+    ///
+    /// assert_eq!(scanner.file_name(), Some("example.txt"));
+    /// assert_eq!(err.to_string(), "unterminated '(' at example.txt:2:1");
+    /// ```
+    #[inline]
+    pub fn with_file_name(source_str: &'src str, name: impl Into<Arc<str>>) -> Self {
+        Self {
+            file_name: Some(name.into()),
+            ..Self::new(source_str)
         }
     }
 
@@ -39,31 +110,86 @@ impl<'src> Scanner<'src> {
         self.head
     }
 
+    /// Returns an iterator that consumes and yields characters at the start of
+    /// the remaining string while they satisfy a condition.
+    ///
+    /// The first character that does not satisfy the given `predicate` is not
+    /// consumed. If the iterator is dropped early, the scanner is positioned
+    /// after the last character yielded.
+    #[inline]
+    pub fn chars_while<'a>(
+        &'a mut self,
+        mut predicate: impl FnMut(char) -> bool + 'a,
+    ) -> impl Iterator<Item = char> + 'a {
+        iter::from_fn(move || self.take_char_if(&mut predicate))
+    }
+
+    /// Saves the current state of the scanner, so that it can be restored later
+    /// with [`Scanner::restore`].
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint<'src> {
+        Checkpoint {
+            scanner: self.clone(),
+        }
+    }
+
     /// Gets the current column number.
     ///
     /// This is the number of code points since the beginning of the line,
     /// starting from 1.
+    ///
+    /// This value is not suitable for indexing into the source string; use
+    /// [`Scanner::column_byte`] for that instead.
     #[inline]
     pub fn column(&self) -> usize {
         self.column
     }
 
+    /// Gets the current byte column.
+    ///
+    /// This is the number of bytes since the beginning of the line, starting
+    /// from 0. Unlike [`Scanner::column`], it counts bytes rather than code
+    /// points, so it can be used to index into the current line.
+    #[inline]
+    pub fn column_byte(&self) -> usize {
+        unsafe { (self.head as usize).unchecked_sub(self.line_start as usize) }
+    }
+
     /// Consumes the next character in the string without checking that one
     /// exists.
     unsafe fn consume_char_unchecked(&mut self) {
         unsafe {
+            self.head = self.tail.as_str().as_ptr();
+
             if self.peek.unwrap_unchecked() == '\n' {
                 self.line += 1;
                 self.column = 1;
+                self.line_start = self.head;
             } else {
                 self.column += 1;
             }
 
-            self.head = self.tail.as_str().as_ptr();
             self.peek = self.tail.next();
         }
     }
 
+    /// Consumes characters in the string until the next non-whitespace
+    /// character or line terminator.
+    fn consume_inline_whitespace(&mut self) {
+        unsafe {
+            while let Some(ch) = self.peek
+                && ch != '\n'
+                && ch.is_whitespace()
+            {
+                if ch == '\r' && self.tail.as_str().starts_with('\n') {
+                    return;
+                }
+
+                self.consume_char_unchecked();
+            }
+        }
+    }
+
     /// Consumes the current line, including the newline character.
     fn consume_line(&mut self) {
         while let Some(ch) = self.take_char() {
@@ -84,10 +210,106 @@ impl<'src> Scanner<'src> {
         }
     }
 
+    /// Consumes characters at the start of the remaining string that satisfy a
+    /// condition and returns the number of bytes consumed.
+    ///
+    /// This is equivalent to the length of the slice returned by
+    /// [`Scanner::take_while`], for callers that only need to know how far the
+    /// scanner advanced. Returns `0` if the remaining string is empty or starts
+    /// with a character that does not satisfy the given `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// let mut scanner = Scanner::new("ünï code");
+    ///
+    /// assert_eq!(scanner.consume_while_len(char::is_alphabetic), 5);
+    /// assert_eq!(scanner.remaining_str(), " code");
+    /// ```
+    #[inline]
+    pub fn consume_while_len(&mut self, predicate: impl FnMut(char) -> bool) -> usize {
+        let from = self.head;
+
+        self.consume_while(predicate);
+
+        unsafe { (self.head as usize).unchecked_sub(from as usize) }
+    }
+
     /// Consumes characters in the string until the next non-whitespace
     /// character.
     fn consume_whitespace(&mut self) {
-        self.consume_while(char::is_whitespace);
+        loop {
+            // ASCII whitespace is consumed byte by byte without decoding chars
+
+            let remaining = self.remaining_str().as_bytes();
+            let mut len = 0;
+
+            while let Some(&byte) = remaining.get(len)
+                && matches!(byte, b'\t'..=b'\r' | b' ')
+            {
+                len += 1;
+
+                if byte == b'\n' {
+                    self.line += 1;
+                    self.column = 1;
+                    self.line_start = unsafe { self.head.add(len) };
+                } else {
+                    self.column += 1;
+                }
+            }
+
+            if len != 0 {
+                unsafe {
+                    self.seek_unchecked(self.head.add(len));
+                }
+            }
+
+            // fall back to decoding non-ASCII chars, then continue with any
+            // ASCII whitespace after them
+
+            match self.peek {
+                Some(ch) if !ch.is_ascii() && ch.is_whitespace() => unsafe {
+                    self.consume_char_unchecked();
+                },
+
+                _ => return,
+            }
+        }
+    }
+
+    /// Creates an error of the given kind at the current location.
+    #[inline]
+    fn error(&self, kind: ScanErrorKind) -> ScanError {
+        self.error_at(kind, self.location())
+    }
+
+    /// Creates an error of the given kind at a location.
+    fn error_at(&self, kind: ScanErrorKind, location: Location) -> ScanError {
+        let err = ScanError::new(kind, location);
+
+        match &self.file_name {
+            Some(name) => err.with_file_name(name.clone()),
+            None => err,
+        }
+    }
+
+    /// Gets the name of the file the source string was read from, if it was
+    /// given with [`Scanner::with_file_name`].
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Returns the [`char`] value of the previous character in the string,
+    /// which is usually the last character consumed.
+    ///
+    /// Returns [`None`] if the scanner is at the beginning of the string. This
+    /// decodes the character before the current position, which takes constant
+    /// time.
+    #[inline]
+    pub fn last_char(&self) -> Option<char> {
+        self.preceding_str().chars().next_back()
     }
 
     /// Gets the current line number.
@@ -99,6 +321,40 @@ impl<'src> Scanner<'src> {
         self.line
     }
 
+    /// Gets the current location in the string.
+    #[inline]
+    pub fn location(&self) -> Location {
+        Location {
+            position: self.position(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    /// Returns the number of leading bytes of `expected` that match the start
+    /// of the remaining string, without consuming anything.
+    ///
+    /// The result is always on a char boundary of `expected`, and is equal to
+    /// `expected.len()` if the remaining string starts with all of `expected`.
+    #[inline]
+    pub fn match_prefix(&self, expected: &str) -> usize {
+        expected
+            .chars()
+            .zip(self.remaining_str().chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(ch, _)| ch.len_utf8())
+            .sum()
+    }
+
+    /// Returns the value of the next byte in the string, without consuming it.
+    ///
+    /// Returns [`None`] if the remaining string is empty. The returned byte may
+    /// be the first byte of a multi-byte character.
+    #[inline]
+    pub fn peek_byte(&self) -> Option<u8> {
+        self.remaining_str().as_bytes().first().copied()
+    }
+
     /// Returns the [`char`] value of the next character in the string, without
     /// consuming it.
     ///
@@ -108,6 +364,22 @@ impl<'src> Scanner<'src> {
         self.peek
     }
 
+    /// Returns a reference to the slice of the remaining string up to the end of
+    /// the current line, without consuming it.
+    ///
+    /// This is the slice that [`Scanner::take_line`] would return: it starts
+    /// at the current position rather than the beginning of the line, and
+    /// includes the newline character, if present.
+    #[inline]
+    pub fn peek_line(&self) -> &'src str {
+        let remaining = self.remaining_str();
+
+        match remaining.find('\n') {
+            Some(i) => &remaining[..=i],
+            None => remaining,
+        }
+    }
+
     /// Gets the current position in the string.
     ///
     /// This is the byte offset from the start of the string.
@@ -134,6 +406,15 @@ impl<'src> Scanner<'src> {
         unsafe { tail_str.len() + (tail_str.as_ptr() as usize).unchecked_sub(self.head as usize) }
     }
 
+    /// Returns an iterator over the lines of the remaining string, without
+    /// consuming them.
+    ///
+    /// Lines are split the same way as [`str::lines`].
+    #[inline]
+    pub fn remaining_lines(&self) -> str::Lines<'src> {
+        self.remaining_str().lines()
+    }
+
     /// Returns a reference to the slice of the original source string that has
     /// not yet been scanned.
     ///
@@ -143,6 +424,192 @@ impl<'src> Scanner<'src> {
         unsafe { str::from_utf8_unchecked(slice::from_raw_parts(self.head, self.remaining_len())) }
     }
 
+    /// Rewinds the scanner to the beginning of the source string.
+    ///
+    /// To rewind to an arbitrary position instead, save a [`Checkpoint`] at that
+    /// position and restore it later.
+    #[inline]
+    pub fn reset(&mut self) {
+        let source = self.source();
+
+        *self = Self {
+            file_name: self.file_name.take(),
+            ..Self::new(source)
+        };
+    }
+
+    /// Restores the state of the scanner that was saved in a [`Checkpoint`].
+    ///
+    /// The position, line, and column numbers are all restored, so scanning
+    /// continues as if nothing after the checkpoint had been consumed.
+    #[inline]
+    pub fn restore(&mut self, checkpoint: &Checkpoint<'src>) {
+        self.clone_from(&checkpoint.scanner);
+    }
+
+    /// Moves the scanner back to the start of the current line.
+    ///
+    /// The line number is unchanged and the column number is reset to 1.
+    #[inline]
+    pub fn rewind_to_line_start(&mut self) {
+        unsafe {
+            self.seek_unchecked(self.line_start);
+        }
+
+        self.column = 1;
+    }
+
+    /// Folds characters at the start of the remaining string into an
+    /// accumulator, consuming each character for which `f` returns
+    /// [`ControlFlow::Continue`].
+    ///
+    /// Scanning stops at the end of the string or at the first character for
+    /// which `f` returns [`ControlFlow::Break`], which is not consumed. Returns
+    /// the final value of the accumulator.
+    pub fn scan_fold<T>(&mut self, init: T, mut f: impl FnMut(T, char) -> ControlFlow<T, T>) -> T {
+        let mut acc = init;
+
+        unsafe {
+            while let Some(ch) = self.peek {
+                match f(acc, ch) {
+                    ControlFlow::Continue(next) => acc = next,
+                    ControlFlow::Break(last) => return last,
+                }
+
+                self.consume_char_unchecked();
+            }
+        }
+
+        acc
+    }
+
+    /// Returns an iterator that consumes each remaining character in the
+    /// string and yields it along with its [`Location`].
+    ///
+    /// The scanner remains usable after the iterator is dropped, positioned
+    /// after the last character yielded.
+    #[inline]
+    pub fn scan_positions(&mut self) -> impl Iterator<Item = (Location, char)> + '_ {
+        iter::from_fn(|| {
+            let location = self.location();
+
+            Some((location, self.take_char()?))
+        })
+    }
+
+    /// Moves the current position to the given pointer without updating the
+    /// line and column numbers.
+    ///
+    /// # Safety
+    ///
+    /// The given pointer must be inside the source string, or at its end, and
+    /// on a char boundary.
+    unsafe fn seek_unchecked(&mut self, to: *const u8) {
+        unsafe {
+            let end = self.head.add(self.remaining_len());
+
+            self.head = to;
+            self.tail = str::from_utf8_unchecked(slice::from_raw_parts(
+                to,
+                (end as usize).unchecked_sub(to as usize),
+            ))
+            .chars();
+            self.peek = self.tail.next();
+        }
+    }
+
+    /// Consumes a byte order mark (U+FEFF) at the beginning of the source
+    /// string, if present.
+    ///
+    /// The byte order mark is not counted as a column. Returns `false` if the
+    /// scanner is not at the beginning of the string or the string does not
+    /// start with a byte order mark.
+    pub fn skip_bom(&mut self) -> bool {
+        if self.head != self.start || self.peek != Some('\u{FEFF}') {
+            return false;
+        }
+
+        unsafe {
+            self.consume_char_unchecked();
+        }
+
+        self.column = 1;
+        self.line_start = self.head;
+        self.bom_skipped = true;
+
+        true
+    }
+
+    /// Consumes the current line in the string, including the newline
+    /// character.
+    ///
+    /// This is equivalent to [`Scanner::take_line`] but does not return the
+    /// consumed slice.
+    #[inline]
+    pub fn skip_line(&mut self) {
+        self.consume_line();
+    }
+
+    /// Consumes the first line of the source string if it is a shebang line
+    /// starting with `#!`, such as `#!/usr/bin/env sh`.
+    ///
+    /// Returns `false` without consuming anything if the scanner is not at the
+    /// beginning of the string or the string does not start with `#!`.
+    pub fn skip_shebang(&mut self) -> bool {
+        if self.head != self.start || !self.remaining_str().starts_with("#!") {
+            return false;
+        }
+
+        self.consume_line();
+
+        true
+    }
+
+    /// Consumes characters up to the first occurrence of `stop` that is not
+    /// preceded by `escape`.
+    ///
+    /// This is equivalent to [`Scanner::take_until_char_escaped`] but does not
+    /// return the consumed slice. Returns `true` if an unescaped `stop` was
+    /// found, in which case it is the next character, or `false` if the entire
+    /// remaining string was consumed.
+    #[inline]
+    pub fn skip_until_unescaped_char(&mut self, stop: char, escape: char) -> bool {
+        self.take_until_char_escaped(stop, escape);
+
+        self.peek.is_some()
+    }
+
+    /// Consumes characters at the start of the remaining string that satisfy a
+    /// condition.
+    ///
+    /// This is equivalent to [`Scanner::take_while`] but does not return the
+    /// consumed slice.
+    #[inline]
+    pub fn skip_while(&mut self, predicate: impl FnMut(char) -> bool) {
+        self.consume_while(predicate);
+    }
+
+    /// Consumes whitespace characters at the start of the remaining string.
+    ///
+    /// This is equivalent to [`Scanner::take_whitespace`] but does not return
+    /// the consumed slice.
+    #[inline]
+    pub fn skip_whitespace(&mut self) {
+        self.consume_whitespace();
+    }
+
+    /// Consumes whitespace characters at the start of the remaining string and
+    /// then runs a function with the scanner, returning its result.
+    ///
+    /// The whitespace stays consumed regardless of the result of the function,
+    /// so this method expresses optional whitespace before a token.
+    #[inline]
+    pub fn skip_whitespace_then<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.consume_whitespace();
+
+        f(self)
+    }
+
     /// Returns a slice of the source string that starts at a given pointer and
     /// ends at the current position.
     ///
@@ -160,6 +627,65 @@ impl<'src> Scanner<'src> {
         }
     }
 
+    /// Returns a reference to the entire original source string.
+    ///
+    /// This is the concatenation of [`Scanner::preceding_str`] and
+    /// [`Scanner::remaining_str`], so it can be indexed with byte positions
+    /// obtained from [`Scanner::position`].
+    #[inline]
+    pub fn source(&self) -> &'src str {
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.start,
+                self.position() + self.remaining_len(),
+            ))
+        }
+    }
+
+    /// Runs a function with the scanner and returns its result along with a
+    /// reference to the slice of the source string it consumed.
+    ///
+    /// Returns `""` as the slice if the function did not consume anything or
+    /// moved the scanner backward.
+    #[inline]
+    pub fn spanned<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> (T, &'src str) {
+        let from = self.head;
+        let value = f(self);
+
+        // the function may have moved the scanner anywhere, so the slice must
+        // be bounds checked
+        let consumed = (from as usize)
+            .checked_sub(self.start as usize)
+            .and_then(|offset| self.preceding_str().get(offset..))
+            .unwrap_or("");
+
+        (value, consumed)
+    }
+
+    /// Returns references to the slices of the original source string before
+    /// and after the current position.
+    ///
+    /// This is equivalent to [`Scanner::preceding_str`] and
+    /// [`Scanner::remaining_str`]. Together, the two slices make up the entire
+    /// [`Scanner::source`].
+    #[inline]
+    pub fn split(&self) -> (&'src str, &'src str) {
+        (self.preceding_str(), self.remaining_str())
+    }
+
+    /// Consumes the next byte in the string and returns its value if it is an
+    /// ASCII character.
+    ///
+    /// Returns [`None`] if the remaining string is empty or starts with a
+    /// non-ASCII character, which is never partially consumed so that line and
+    /// column tracking stay correct.
+    #[inline]
+    pub fn take_byte(&mut self) -> Option<u8> {
+        let ch = self.take_char_if(|ch| ch.is_ascii())?;
+
+        Some(ch as u8)
+    }
+
     /// Consumes the next character in the string and returns its [`char`]
     /// value.
     ///
@@ -213,11 +739,228 @@ impl<'src> Scanner<'src> {
         true
     }
 
+    /// Applies a function to the next character in the string and consumes it
+    /// if the function returns [`Some`].
+    ///
+    /// Returns [`None`] if the remaining string is empty or the given function
+    /// returns [`None`], in which case nothing is consumed.
+    #[inline]
+    pub fn take_char_map<T>(&mut self, f: impl FnOnce(char) -> Option<T>) -> Option<T> {
+        let value = f(self.peek?)?;
+
+        unsafe {
+            self.consume_char_unchecked();
+        }
+
+        Some(value)
+    }
+
+    /// Consumes a region that starts with `open` and ends with the next
+    /// occurrence of `close`, and returns a reference to the slice between the
+    /// delimiters.
+    ///
+    /// Regions do not nest, so this is suited to strings and other regions
+    /// whose contents cannot contain `close`. Returns an error at the current
+    /// location if the remaining string does not start with `open`, or at the
+    /// location of `open` if the region is not closed. Nothing is consumed if
+    /// an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::{ScanErrorKind, Scanner};
+    /// let mut scanner = Scanner::new("'multi\nline' rest");
+    ///
+    /// assert_eq!(scanner.take_delimited('\'', '\''), Ok("multi\nline"));
+    /// assert_eq!(scanner.remaining_str(), " rest");
+    /// assert_eq!(scanner.line(), 2);
+    ///
+    /// let mut scanner = Scanner::new("(unterminated");
+    /// let err = scanner.take_delimited('(', ')').unwrap_err();
+    ///
+    /// assert_eq!(err.kind(), ScanErrorKind::Unterminated('('));
+    /// assert_eq!(err.location().column, 1);
+    /// ```
+    pub fn take_delimited(&mut self, open: char, close: char) -> Result<&'src str, ScanError> {
+        if self.peek != Some(open) {
+            return Err(self.error(ScanErrorKind::Expected(open)));
+        }
+
+        let location = self.location();
+        let mut scanner = self.clone();
+
+        scanner.take_char();
+
+        let contents = scanner.take_while(|ch| ch != close);
+
+        if !scanner.take_char_if_eq(close) {
+            return Err(self.error_at(ScanErrorKind::Unterminated(open), location));
+        }
+
+        *self = scanner;
+
+        Ok(contents)
+    }
+
+    /// Consumes between `min` and `max` hexadecimal digits and returns their
+    /// value.
+    fn take_hex_digits(&mut self, min: usize, max: usize) -> Result<u32, ScanError> {
+        let mut value = 0;
+
+        for i in 0..max {
+            match self.take_char_map(|ch| ch.to_digit(16)) {
+                Some(digit) => value = value << 4 | digit,
+                None if i < min => return Err(self.error(ScanErrorKind::ExpectedHexDigit)),
+                None => break,
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Runs a function that scans part of the remaining string, and restores
+    /// the scanner to its previous state if the function returns [`None`].
+    ///
+    /// This is useful for speculatively parsing alternatives without restoring
+    /// a [`Checkpoint`] on every failure path. The line and column numbers are
+    /// restored along with the position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// let mut scanner = Scanner::new("let x");
+    ///
+    /// let fn_keyword = scanner.take_if(|s| s.take_while(char::is_alphabetic).eq("fn").then_some(()));
+    ///
+    /// assert_eq!(fn_keyword, None);
+    /// assert_eq!(scanner.remaining_str(), "let x");
+    ///
+    /// let let_keyword = scanner.take_if(|s| s.take_while(char::is_alphabetic).eq("let").then_some(()));
+    ///
+    /// assert_eq!(let_keyword, Some(()));
+    /// assert_eq!(scanner.remaining_str(), " x");
+    /// ```
+    pub fn take_if<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+
+        if result.is_none() {
+            self.restore(&checkpoint);
+        }
+
+        result
+    }
+
+    /// Consumes spaces and tabs at the start of the current line and returns
+    /// the width of the indentation along with a reference to the slice that
+    /// contains it.
+    ///
+    /// Spaces count as one column and tabs advance to the next multiple of
+    /// `tab_width`. Returns `(0, "")` if the scanner is not at the start of a
+    /// line or the line is not indented.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tab_width` is `0` and the indentation contains a tab.
+    pub fn take_indentation(&mut self, tab_width: usize) -> (usize, &'src str) {
+        if self.head != self.line_start {
+            return (0, "");
+        }
+
+        let mut width = 0;
+
+        let indentation = self.take_while(|ch| match ch {
+            ' ' => {
+                width += 1;
+                true
+            }
+
+            '\t' => {
+                width += tab_width - width % tab_width;
+                true
+            }
+
+            _ => false,
+        });
+
+        (width, indentation)
+    }
+
+    /// Consumes whitespace characters at the start of the remaining string, up
+    /// to the next line terminator, and returns a reference to the slice that
+    /// contains them.
+    ///
+    /// Unlike [`Scanner::take_whitespace`], this method does not consume `\n`
+    /// or `\r\n`. Returns `""` if the remaining string is empty or starts with
+    /// a non-whitespace character or a line terminator.
+    #[inline]
+    pub fn take_inline_whitespace(&mut self) -> &'src str {
+        let from = self.head;
+
+        self.consume_inline_whitespace();
+
+        unsafe { self.slice_back_unchecked(from) }
+    }
+
+    /// Consumes an optionally signed decimal integer at the start of the
+    /// remaining string and parses it into any type that implements
+    /// [`FromStr`].
+    ///
+    /// Returns [`None`] without consuming anything if the remaining string
+    /// does not start with at least one ASCII digit, optionally preceded by `+`
+    /// or `-`. Otherwise, the integer is consumed and the result of parsing it
+    /// is returned, which reports errors such as overflow.
+    pub fn take_int<T: FromStr>(&mut self) -> Option<Result<T, T::Err>> {
+        let bytes = self.remaining_str().as_bytes();
+        let sign_len = matches!(bytes.first(), Some(b'+' | b'-')) as usize;
+
+        let digits_len = bytes[sign_len..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+
+        if digits_len == 0 {
+            return None;
+        }
+
+        let from = self.head;
+
+        unsafe {
+            // every byte of the integer is an ASCII char
+            for _ in 0..sign_len + digits_len {
+                self.consume_char_unchecked();
+            }
+        }
+
+        Some(unsafe { self.slice_back_unchecked(from) }.parse())
+    }
+
     /// Consumes the current line in the string and returns a reference to the
     /// slice that contains it.
     ///
     /// The newline character is included, if present. Returns `""` if the
     /// remaining string is empty.
+    ///
+    /// Afterward, the scanner is positioned at the start of the next line and
+    /// the line number has been incremented exactly once, which makes this
+    /// method suitable for recovering from parse errors by skipping the rest of
+    /// the current line. The exception is the last line when it has no newline
+    /// character: the scanner is then at the end of the string and the line
+    /// number is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// let mut scanner = Scanner::new("first\nlast");
+    ///
+    /// assert_eq!(scanner.take_line(), "first\n");
+    /// assert_eq!(scanner.line(), 2);
+    ///
+    /// assert_eq!(scanner.take_line(), "last");
+    /// assert_eq!((scanner.line(), scanner.column()), (2, 5));
+    /// ```
     #[inline]
     pub fn take_line(&mut self) -> &'src str {
         let from = self.head;
@@ -227,6 +970,200 @@ impl<'src> Scanner<'src> {
         unsafe { self.slice_back_unchecked(from) }
     }
 
+    /// Consumes the current line in the string and returns references to the
+    /// slices that contain its content and its line terminator.
+    ///
+    /// The terminator is `"\r\n"`, `"\n"`, or `""` if the line ends at the end
+    /// of the string. Returns `("", "")` if the remaining string is empty.
+    #[inline]
+    pub fn take_line_parts(&mut self) -> (&'src str, &'src str) {
+        let line = self.take_line();
+
+        let terminator_len = if line.ends_with("\r\n") {
+            2
+        } else if line.ends_with('\n') {
+            1
+        } else {
+            0
+        };
+
+        line.split_at(line.len() - terminator_len)
+    }
+
+    /// Consumes exactly `n` bytes and returns a reference to the slice that
+    /// contains them.
+    ///
+    /// Returns [`None`] without consuming anything if `n` is greater than the
+    /// length of the remaining string or is not on a char boundary.
+    pub fn take_n_bytes(&mut self, n: usize) -> Option<&'src str> {
+        if !self.remaining_str().is_char_boundary(n) {
+            return None;
+        }
+
+        let from = self.head;
+
+        unsafe {
+            let to = from.add(n);
+
+            while self.head < to {
+                self.consume_char_unchecked();
+            }
+
+            Some(self.slice_back_unchecked(from))
+        }
+    }
+
+    /// Consumes a numeric escape sequence and returns the [`char`] it encodes.
+    ///
+    /// This method should be called after consuming the character that starts
+    /// an escape sequence, such as `\`. The following forms are recognized:
+    ///
+    /// * `xNN`, with exactly 2 hexadecimal digits.
+    /// * `uNNNN`, with exactly 4 hexadecimal digits.
+    /// * `u{N}`, with 1 to 6 hexadecimal digits.
+    ///
+    /// Returns an error if the remaining string does not start with one of
+    /// these forms or if the encoded value is not a valid code point, such as a
+    /// lone surrogate or a value greater than U+10FFFF. Nothing is consumed if
+    /// an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::{ScanErrorKind, Scanner};
+    /// for (escape, expected) in [("x41", 'A'), ("u0041", 'A'), ("u{1F600}", '😀')] {
+    ///     assert_eq!(Scanner::new(escape).take_numeric_escape(), Ok(expected));
+    /// }
+    ///
+    /// for (escape, kind, column) in [
+    ///     ("uD800", ScanErrorKind::InvalidCodePoint(0xD800), 1),
+    ///     ("u{110000}", ScanErrorKind::InvalidCodePoint(0x110000), 1),
+    ///     ("u{}", ScanErrorKind::ExpectedHexDigit, 3),
+    ///     ("x4", ScanErrorKind::ExpectedHexDigit, 3),
+    ///     ("u{1234567}", ScanErrorKind::Expected('}'), 9),
+    ///     ("q", ScanErrorKind::InvalidEscape, 1),
+    /// ] {
+    ///     let mut scanner = Scanner::new(escape);
+    ///     let err = scanner.take_numeric_escape().unwrap_err();
+    ///
+    ///     assert_eq!((err.kind(), err.location().column), (kind, column));
+    ///     assert_eq!(scanner.position(), 0);
+    /// }
+    /// ```
+    pub fn take_numeric_escape(&mut self) -> Result<char, ScanError> {
+        let location = self.location();
+        let mut scanner = self.clone();
+
+        let value = match scanner.peek {
+            Some('x') => {
+                scanner.take_char();
+                scanner.take_hex_digits(2, 2)?
+            }
+
+            Some('u') => {
+                scanner.take_char();
+
+                if scanner.take_char_if_eq('{') {
+                    let value = scanner.take_hex_digits(1, 6)?;
+
+                    if !scanner.take_char_if_eq('}') {
+                        return Err(scanner.error(ScanErrorKind::Expected('}')));
+                    }
+
+                    value
+                } else {
+                    scanner.take_hex_digits(4, 4)?
+                }
+            }
+
+            _ => return Err(scanner.error(ScanErrorKind::InvalidEscape)),
+        };
+
+        let ch = char::from_u32(value)
+            .ok_or_else(|| self.error_at(ScanErrorKind::InvalidCodePoint(value), location))?;
+
+        *self = scanner;
+
+        Ok(ch)
+    }
+
+    /// Consumes up to `max` consecutive occurrences of a character and returns
+    /// how many were consumed.
+    ///
+    /// Returns `0` if `max` is `0` or the remaining string does not start with
+    /// the given character.
+    #[inline]
+    pub fn take_repeated_char(&mut self, ch: char, max: usize) -> usize {
+        let mut count = 0;
+
+        while count < max && self.take_char_if_eq(ch) {
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Consumes characters up to the end of the current line and returns a
+    /// reference to the slice that contains them.
+    ///
+    /// Unlike [`Scanner::take_line`], the line terminator (`\n` or `\r\n`) is
+    /// not consumed. Returns `""` if the remaining string is empty or starts
+    /// with a line terminator.
+    pub fn take_to_end_of_line(&mut self) -> &'src str {
+        let from = self.head;
+
+        unsafe {
+            while let Some(ch) = self.peek
+                && ch != '\n'
+            {
+                if ch == '\r' && self.tail.as_str().starts_with('\n') {
+                    break;
+                }
+
+                self.consume_char_unchecked();
+            }
+
+            self.slice_back_unchecked(from)
+        }
+    }
+
+    /// Consumes characters up to the first occurrence of any of the given
+    /// `stops` and returns a reference to the slice that contains them.
+    ///
+    /// The matching stop character is not consumed. Returns the entire
+    /// remaining string if none of the `stops` occur in it.
+    #[inline]
+    pub fn take_until_any(&mut self, stops: &[char]) -> &'src str {
+        self.take_while(|ch| !stops.contains(&ch))
+    }
+
+    /// Consumes characters up to the first occurrence of `stop` that is not
+    /// preceded by `escape` and returns a reference to the slice that contains
+    /// them.
+    ///
+    /// Each `escape` character makes the character after it literal, so an
+    /// escaped `escape` does not escape the following character. Escape
+    /// sequences are included in the returned slice as-is. The `stop` character
+    /// is not consumed. Returns the entire remaining string if no unescaped
+    /// `stop` occurs in it.
+    pub fn take_until_char_escaped(&mut self, stop: char, escape: char) -> &'src str {
+        let from = self.head;
+
+        unsafe {
+            while let Some(ch) = self.peek
+                && ch != stop
+            {
+                self.consume_char_unchecked();
+
+                if ch == escape && self.peek.is_some() {
+                    self.consume_char_unchecked();
+                }
+            }
+
+            self.slice_back_unchecked(from)
+        }
+    }
+
     /// Consumes characters at the start of the remaining string that satisfy a
     /// condition and returns a reference to the slice that contains them.
     ///
@@ -241,11 +1178,120 @@ impl<'src> Scanner<'src> {
         unsafe { self.slice_back_unchecked(from) }
     }
 
+    /// Consumes at most `max` characters at the start of the remaining string
+    /// that satisfy a condition and returns a reference to the slice that
+    /// contains them.
+    ///
+    /// Returns `""` if `max` is `0`, the remaining string is empty, or it starts
+    /// with a character that does not satisfy the given `condition`.
+    #[inline]
+    pub fn take_while_bounded(
+        &mut self,
+        max: usize,
+        mut predicate: impl FnMut(char) -> bool,
+    ) -> &'src str {
+        let mut remaining = max;
+
+        self.take_while(|ch| {
+            if remaining == 0 || !predicate(ch) {
+                return false;
+            }
+
+            remaining -= 1;
+
+            true
+        })
+    }
+
+    /// Consumes characters at the start of the remaining string that are in a
+    /// [`CharClass`] and returns a reference to the slice that contains them.
+    ///
+    /// Returns `""` if the remaining string is empty or starts with a character
+    /// that is not in the given `class`.
+    #[inline]
+    pub fn take_while_class(&mut self, class: &CharClass) -> &'src str {
+        self.take_while(|ch| class.contains(ch))
+    }
+
+    /// Consumes characters at the start of the remaining string that satisfy a
+    /// condition and returns a reference to the slice that contains them along
+    /// with the number of characters in it.
+    ///
+    /// Returns `("", 0)` if the remaining string is empty or starts with a
+    /// character that does not satisfy the given `condition`.
+    #[inline]
+    pub fn take_while_counted(
+        &mut self,
+        mut predicate: impl FnMut(char) -> bool,
+    ) -> (&'src str, usize) {
+        let from = self.head;
+        let mut count = 0;
+
+        self.consume_while(|ch| {
+            let matched = predicate(ch);
+
+            count += matched as usize;
+
+            matched
+        });
+
+        (unsafe { self.slice_back_unchecked(from) }, count)
+    }
+
+    /// Consumes characters at the start of the remaining string that satisfy a
+    /// condition and returns a reference to the slice that contains them, along
+    /// with the first character that did not satisfy it.
+    ///
+    /// The returned character is not consumed, and is [`None`] if the end of
+    /// the string was reached. This is equivalent to [`Scanner::take_while`]
+    /// followed by [`Scanner::peek_char`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// let mut scanner = Scanner::new("key=value");
+    ///
+    /// assert_eq!(scanner.take_while_ext(char::is_alphanumeric), ("key", Some('=')));
+    /// assert_eq!(scanner.remaining_str(), "=value");
+    /// ```
+    #[inline]
+    pub fn take_while_ext(
+        &mut self,
+        predicate: impl FnMut(char) -> bool,
+    ) -> (&'src str, Option<char>) {
+        let slice = self.take_while(predicate);
+
+        (slice, self.peek)
+    }
+
+    /// Consumes characters at the start of the remaining string that satisfy a
+    /// stateful condition and returns a reference to the slice that contains
+    /// them.
+    ///
+    /// The given `predicate` can update `state`, which remains available to the
+    /// caller afterward. Returns `""` if the remaining string is empty or starts
+    /// with a character that does not satisfy the given `predicate`.
+    #[inline]
+    pub fn take_while_stateful<S>(
+        &mut self,
+        state: &mut S,
+        mut predicate: impl FnMut(&mut S, char) -> bool,
+    ) -> &'src str {
+        self.take_while(|ch| predicate(state, ch))
+    }
+
     /// Consumes whitespace characters at the start of the remaining string and
     /// returns a reference to the slice that contains them.
     ///
-    /// Returns `""` if the remaining string is empty or starts with a
-    /// non-whitespace character.
+    /// Whitespace is defined by [`char::is_whitespace`]. Returns `""` if the
+    /// remaining string is empty or starts with a non-whitespace character.
+    ///
+    /// # Performance
+    ///
+    /// ASCII whitespace is consumed without decoding each character, so this
+    /// method is faster than [`Scanner::take_while`] with
+    /// [`char::is_whitespace`] for typical indentation.
     #[inline]
     pub fn take_whitespace(&mut self) -> &'src str {
         let from = self.head;
@@ -254,12 +1300,96 @@ impl<'src> Scanner<'src> {
 
         unsafe { self.slice_back_unchecked(from) }
     }
+
+    /// Returns a reference to the slice of the source string that has been
+    /// consumed since a [`Checkpoint`] was saved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint was not saved from a scanner of the same source
+    /// string, or if it is after the current position.
+    pub fn text_since(&self, checkpoint: &Checkpoint<'src>) -> &'src str {
+        let from = checkpoint.scanner.head;
+
+        assert!(
+            checkpoint.scanner.start == self.start && from <= self.head,
+            "checkpoint must be at or before the current position in the same source string"
+        );
+
+        unsafe { self.slice_back_unchecked(from) }
+    }
+
+    /// Moves the scanner to a byte offset from the start of the source string,
+    /// which may be before or after the current position.
+    ///
+    /// The line and column numbers are recomputed from the start of the
+    /// string. If a byte order mark was skipped with [`Scanner::skip_bom`], it
+    /// is not counted as a column, the same as when scanning forward. Returns
+    /// `false` without moving the scanner if `offset` is past the end of the
+    /// string or not on a char boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use lexlib::text::Scanner;
+    /// for skip_bom in [false, true] {
+    ///     let mut scanner = Scanner::new("\u{FEFF}ab\ncd");
+    ///
+    ///     if skip_bom {
+    ///         scanner.skip_bom();
+    ///     }
+    ///
+    ///     scanner.take_char();
+    ///     scanner.take_char();
+    ///
+    ///     let saved = (scanner.line(), scanner.column(), scanner.column_byte());
+    ///     let position = scanner.position();
+    ///
+    ///     scanner.take_line();
+    ///
+    ///     assert!(scanner.try_set_position(position));
+    ///     assert_eq!((scanner.line(), scanner.column(), scanner.column_byte()), saved);
+    /// }
+    /// ```
+    pub fn try_set_position(&mut self, offset: usize) -> bool {
+        let source = self.source();
+
+        if !source.is_char_boundary(offset) {
+            return false;
+        }
+
+        let preceding = &source[..offset];
+
+        // the first line starts after the byte order mark, if it was skipped
+        let bom_len = match self.bom_skipped && preceding.starts_with('\u{FEFF}') {
+            true => '\u{FEFF}'.len_utf8(),
+            false => 0,
+        };
+
+        let line_offset = preceding.rfind('\n').map_or(bom_len, |i| i + 1);
+
+        unsafe {
+            self.seek_unchecked(self.start.add(offset));
+            self.line_start = self.start.add(line_offset);
+        }
+
+        // moving to the start of the string puts the byte order mark back
+        if offset == 0 {
+            self.bom_skipped = false;
+        }
+
+        self.line = preceding.matches('\n').count() + 1;
+        self.column = preceding[line_offset..].chars().count() + 1;
+
+        true
+    }
 }
 
 impl fmt::Debug for Scanner<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Scanner")
             .field("column", &self.column)
+            .field("file_name", &self.file_name)
             .field("line", &self.line)
             .field("peek_char", &self.peek)
             .field("position", &self.position())